@@ -0,0 +1,85 @@
+use std::path::PathBuf;
+
+/// User-facing configuration for a sheep_it release.
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    pub repository: RepoConfig,
+    pub auth: AuthConfig,
+    pub subprojects: Vec<SubprojectConfig>,
+    /// Explicit GPG signing key id; falls back to the repo's `user.signingkey`.
+    pub signing_key: Option<String>,
+    /// Explicit `gpg` program path; falls back to the repo's `gpg.program`.
+    pub gpg_program: Option<String>,
+}
+
+/// Configuration for how a release touches the repository itself.
+#[derive(Debug, Clone)]
+pub struct RepoConfig {
+    pub enable_branch: bool,
+    pub enable_commit: bool,
+    pub enable_tag: bool,
+    pub enable_push: bool,
+    pub remote_name: String,
+    /// Fetch and fast-forward to upstream before releasing, aborting if the
+    /// local branch has diverged.
+    pub sync_before_release: bool,
+    /// Comma separated glob patterns the current branch must match before a
+    /// release can proceed. See `repo::branch_guard`.
+    pub allow_branch: String,
+    /// GPG-sign the release commit and tag. See `repo::sign`.
+    pub sign: bool,
+}
+
+impl Default for RepoConfig {
+    fn default() -> RepoConfig {
+        RepoConfig {
+            enable_branch: true,
+            enable_commit: true,
+            enable_tag: true,
+            enable_push: true,
+            remote_name: "origin".to_string(),
+            sync_before_release: false,
+            allow_branch: "main,master".to_string(),
+            sign: false,
+        }
+    }
+}
+
+/// Credentials for authenticating against private remotes during clone and
+/// push. All fields are optional so `Config::default()` still works against
+/// public remotes.
+#[derive(Debug, Clone, Default)]
+pub struct AuthConfig {
+    pub username: Option<String>,
+    pub token: Option<String>,
+    pub ssh_key_path: Option<String>,
+    pub allow_interactive_prompt: bool,
+}
+
+/// One independently-versioned component of a monorepo.
+#[derive(Debug, Clone)]
+pub struct SubprojectConfig {
+    pub name: String,
+    pub path: PathBuf,
+    pub version_file: PathBuf,
+    pub tag_prefix: String,
+    pub enable_branch: bool,
+    pub enable_commit: bool,
+    pub enable_tag: bool,
+    pub enable_push: bool,
+}
+
+impl SubprojectConfig {
+    /// Builds this subproject's `RepoConfig`: its own enable_* flags, but
+    /// inheriting cross-cutting settings (remote, sync, branch allowlist,
+    /// signing) from the root repo config.
+    pub fn repo_config(&self, base: &RepoConfig) -> RepoConfig {
+        RepoConfig {
+            enable_branch: self.enable_branch,
+            enable_commit: self.enable_commit,
+            enable_tag: self.enable_tag,
+            enable_push: self.enable_push,
+            ..base.clone()
+        }
+    }
+}
@@ -1,3 +1,4 @@
+mod commit_log;
 mod dryrun;
 mod strings;
 pub mod operation;
@@ -9,15 +10,20 @@ use mockall_double::double;
 use crate::config::{Config, RepoConfig};
 use crate::error::SheepError;
 use crate::project::operation::Operation;
+use crate::repo::auth;
 use crate::repo::clone::GitCloner;
 use crate::repo::open::{GitOpener};
 use crate::repo::path;
 use crate::repo::remote::GitRemotes;
+use crate::repo::sign::SigningConfig;
+use crate::repo::sync;
 
 #[double]
 use crate::project::project_version::ProjectVersion;
+use crate::project::project_version::VersionUpdate;
 use crate::project::strings::ProjectStrings;
 use crate::repo::branch::GitBranches;
+use crate::repo::branch_guard;
 use crate::repo::commit::GitCommits;
 use crate::repo::tag::GitTags;
 
@@ -25,80 +31,190 @@ pub struct Project {
     config: Config,
     repo: Repository,
     is_dry_run_project: bool,
+    // Branch name for the `allow_branch` guard on dry-run projects; see branch_guard::check.
+    source_branch: Option<String>,
 }
 
 impl Project {
     pub fn new_local_project<P: AsRef<Path>>(path: P) -> Result<Project, SheepError> {
+        Self::new_local_project_with_config(path, Config::default())
+    }
+
+    /// Like `new_local_project`, but with an explicit `Config` instead of
+    /// `Config::default()` - e.g. so a caller can inject a CI-provided
+    /// `AuthConfig` token rather than relying on the ambient git credential
+    /// helper.
+    pub fn new_local_project_with_config<P: AsRef<Path>>(
+        path: P,
+        config: Config,
+    ) -> Result<Project, SheepError> {
         let repo = GitOpener::new().open(path)?;
-        let config = Config::default();
         let project = Project {
             config,
             repo,
             is_dry_run_project: false,
+            source_branch: None,
         };
         Ok(project)
     }
 
     pub fn new_remote_project<P: AsRef<Path>>(url: &str, directory: P) -> Result<Project, SheepError> {
+        Self::new_remote_project_with_config(url, directory, Config::default())
+    }
+
+    /// Like `new_remote_project`, but with an explicit `Config` instead of
+    /// `Config::default()` - the clone itself authenticates with
+    /// `config.auth`, so this is the only way to clone a private remote.
+    pub fn new_remote_project_with_config<P: AsRef<Path>>(
+        url: &str,
+        directory: P,
+        config: Config,
+    ) -> Result<Project, SheepError> {
         let repo_path = path::repo_path(url, directory)?;
-        let repo = GitCloner::new().clone(url, repo_path)?;
-        let config = Config::default();
+        let callbacks = auth::callbacks(&config.auth);
+        let repo = GitCloner::new().clone(url, repo_path, callbacks)?;
         let project = Project {
             config,
             repo,
             is_dry_run_project: false,
+            source_branch: None,
         };
         Ok(project)
     }
 
     pub fn new_dry_run_project<P: AsRef<Path>>(path: P) -> Result<Project, SheepError> {
+        Self::new_dry_run_project_with_config(path, Config::default())
+    }
+
+    /// Like `new_dry_run_project`, but with an explicit `Config` instead of
+    /// `Config::default()`.
+    pub fn new_dry_run_project_with_config<P: AsRef<Path>>(
+        path: P,
+        config: Config,
+    ) -> Result<Project, SheepError> {
         let remotes = GitRemotes::new();
-        let local_project = Project::new_local_project(path)?;
+        let local_project = Project::new_local_project_with_config(path, config)?;
         let remote_url = remotes.remote_url(&local_project.repo, "origin")?;
+        let source_branch = local_project.repo.head()?.shorthand().map(str::to_string);
         let directory = dryrun::directory()?;
 
-        let remote_project = Project::new_remote_project(&remote_url, directory)?;
+        let remote_project =
+            Project::new_remote_project_with_config(&remote_url, directory, local_project.config)?;
         let dry_run_project = Project {
-            config: local_project.config,
+            config: remote_project.config,
             is_dry_run_project: true,
             repo: remote_project.repo,
+            source_branch,
         };
         Ok(dry_run_project)
     }
 
     pub fn update(&self, operation: Operation) -> Result<ProjectUpdateInfo, SheepError> {
         let repo_config = &self.config.repository;
-        let project_version = ProjectVersion::new(&self);
-        let version_update = operation.version_update(&project_version);
-        let project_strings = ProjectStrings::new(&self.config, &version_update);
+        branch_guard::check(
+            &self.repo,
+            &repo_config.allow_branch,
+            self.source_branch.as_deref(),
+        )?;
+
+        // The branch/commit HEAD is on before anything below touches it, so
+        // each subproject can be restored to the same starting point rather
+        // than stacking its release on top of the previous one.
+        let base_branch = self.repo.head()?.shorthand().map(str::to_string);
 
-        Self::update_repo(&self.repo, repo_config, &project_strings)?;
-        // Process subprojects if there are any
+        let mut info = ProjectUpdateInfo::new(self.repo.path());
+
+        let project_version = ProjectVersion::new();
+        let version_update = operation.version_update(&self.repo, &project_version)?;
+        if !matches!(version_update, VersionUpdate::NoReleaseNeeded) {
+            let project_strings = ProjectStrings::new(&self.config, &version_update);
+            Self::update_repo(&self.repo, &self.config, repo_config, &project_strings, &[])?;
+            info.record(repo_config, &project_strings);
+        }
+
+        // Process subprojects if there are any, each bumped from its own
+        // version file so independently-versioned components actually
+        // diverge, even if the root project itself had no release needed.
+        for subproject in &self.config.subprojects {
+            Self::restore_base_branch(&self.repo, base_branch.as_deref())?;
+
+            let subproject_version = ProjectVersion::new_for_subproject(subproject);
+            let subproject_version_update = operation.version_update(&self.repo, &subproject_version)?;
+            if matches!(subproject_version_update, VersionUpdate::NoReleaseNeeded) {
+                continue;
+            }
+
+            let subproject_repo_config = subproject.repo_config(repo_config);
+            let subproject_strings = ProjectStrings::new_for_subproject(
+                &self.config,
+                subproject,
+                &subproject_version_update,
+            );
+            Self::update_repo(
+                &self.repo,
+                &self.config,
+                &subproject_repo_config,
+                &subproject_strings,
+                std::slice::from_ref(&subproject.path),
+            )?;
+            info.record(&subproject_repo_config, &subproject_strings);
+        }
 
-        // Return project info
-        let repo_path = self.repo.path();
-        Ok(ProjectUpdateInfo::new(repo_path))
+        Ok(info)
+    }
+
+    /// Checks out `base_branch` (if any), so a subproject release starts
+    /// from the same commit as the root release did rather than from
+    /// whatever branch the previous subproject's release just created.
+    fn restore_base_branch(repo: &Repository, base_branch: Option<&str>) -> Result<(), SheepError> {
+        if let Some(branch_name) = base_branch {
+            GitBranches::new().checkout_branch(repo, branch_name)?;
+        }
+        Ok(())
     }
 
     fn update_repo(
         repo: &Repository,
+        config: &Config,
         repo_config: &RepoConfig,
-        project_strings: &ProjectStrings) -> Result<(), SheepError> {
+        project_strings: &ProjectStrings,
+        paths: &[PathBuf]) -> Result<(), SheepError> {
+        // Fetch and fast-forward to upstream before touching anything
+        if repo_config.sync_before_release {
+            sync::sync(repo, &project_strings.remote_name, &config.auth)?;
+        }
         // Create branch if enabled in configuration
         if repo_config.enable_branch {
             let branches = GitBranches::new();
             branches.create_branch(repo, &project_strings.branch_name)?;
             branches.checkout_branch(repo, &project_strings.branch_name)?;
         }
+        let signing = if repo_config.sign {
+            let signing = SigningConfig::resolve(
+                repo,
+                config.signing_key.as_deref(),
+                config.gpg_program.as_deref(),
+            )
+            .ok_or_else(|| {
+                git2::Error::from_str(
+                    "repository.sign is enabled but no signing key was found in Config \
+                     or the repo's user.signingkey",
+                )
+            })?;
+            Some(signing)
+        } else {
+            None
+        };
+
         // Create commit if enabled in configuration
         if repo_config.enable_commit {
             let commits = GitCommits::new();
-            commits.commit(repo, vec![], &project_strings.commit_message)?;
+            commits.commit(repo, paths.to_vec(), &project_strings.commit_message, signing.as_ref())?;
         }
         // Create tag if enabled in configuration
         if repo_config.enable_tag {
             let tags = GitTags::new();
-            tags.create_tag(repo, &project_strings.tag_name, None)?;
+            tags.create_tag(repo, &project_strings.tag_name, None, signing.as_ref())?;
         }
         // Push if enabled in configuration
         if repo_config.enable_push {
@@ -106,12 +222,14 @@ impl Project {
             if repo_config.enable_branch {
                 remotes.push_branch(repo,
                                     &project_strings.branch_name,
-                                    &project_strings.remote_name)?;
+                                    &project_strings.remote_name,
+                                    auth::callbacks(&config.auth))?;
             }
             if repo_config.enable_tag {
                 remotes.push_tag(repo,
                                  &project_strings.tag_name,
-                                 &project_strings.remote_name)?;
+                                 &project_strings.remote_name,
+                                 auth::callbacks(&config.auth))?;
             }
         }
         Ok(())
@@ -120,12 +238,29 @@ impl Project {
 
 pub struct ProjectUpdateInfo {
     pub repo_path: PathBuf,
+    pub released: bool,
+    pub branches: Vec<String>,
+    pub tags: Vec<String>,
 }
 
 impl ProjectUpdateInfo {
     fn new(repo_path: &Path) -> ProjectUpdateInfo {
         ProjectUpdateInfo {
-            repo_path: repo_path.to_path_buf()
+            repo_path: repo_path.to_path_buf(),
+            released: false,
+            branches: vec![],
+            tags: vec![],
+        }
+    }
+
+    /// Records the branch/tag created for one project or subproject release.
+    fn record(&mut self, repo_config: &RepoConfig, project_strings: &ProjectStrings) {
+        self.released = true;
+        if repo_config.enable_branch {
+            self.branches.push(project_strings.branch_name.clone());
+        }
+        if repo_config.enable_tag {
+            self.tags.push(project_strings.tag_name.clone());
         }
     }
 }
\ No newline at end of file
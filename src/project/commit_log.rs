@@ -0,0 +1,112 @@
+use std::collections::HashSet;
+
+use git2::{Oid, Repository};
+
+use crate::error::SheepError;
+
+/// The version bump implied by a run of Conventional Commits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BumpLevel {
+    Patch,
+    Minor,
+    Major,
+}
+
+/// Walks commits from `HEAD` back to the nearest reachable tag, parsing each
+/// commit summary/body as a Conventional Commit, and returns the highest
+/// bump level implied. Returns `None` if no walked commit implies a bump
+/// (no `feat`, `fix`, `perf` or breaking change), meaning no release is
+/// needed.
+pub fn highest_bump(repo: &Repository) -> Result<Option<BumpLevel>, SheepError> {
+    let tagged = tagged_commit_oids(repo)?;
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push_head()?;
+    // Hide tagged commits up front so libgit2 prunes their whole ancestry.
+    // A `break` on first-tagged-commit-seen would stop the walk too early
+    // when merge commits have unmerged sibling paths still worth visiting.
+    for oid in &tagged {
+        revwalk.hide(*oid)?;
+    }
+
+    let mut highest = None;
+    for oid in revwalk {
+        let oid = oid?;
+        let commit = repo.find_commit(oid)?;
+        let message = commit.message().unwrap_or("");
+        if let Some(bump) = bump_for_message(message) {
+            highest = Some(match highest {
+                Some(current) if current >= bump => current,
+                _ => bump,
+            });
+        }
+    }
+
+    Ok(highest)
+}
+
+fn tagged_commit_oids(repo: &Repository) -> Result<HashSet<Oid>, SheepError> {
+    let mut oids = HashSet::new();
+    for name in repo.tag_names(None)?.iter().flatten() {
+        let reference = repo.find_reference(&format!("refs/tags/{}", name))?;
+        let commit = reference.peel_to_commit()?;
+        oids.insert(commit.id());
+    }
+    Ok(oids)
+}
+
+fn bump_for_message(message: &str) -> Option<BumpLevel> {
+    if message.contains("BREAKING CHANGE:") {
+        return Some(BumpLevel::Major);
+    }
+
+    let summary = message.lines().next().unwrap_or("");
+    let (type_and_bang, _) = summary.split_once(':')?;
+    let is_breaking = type_and_bang.ends_with('!');
+    let type_and_bang = type_and_bang.trim_end_matches('!');
+    // Strip a scope, e.g. `feat(parser)` -> `feat`, the most common
+    // Conventional Commits form.
+    let commit_type = match type_and_bang.find('(') {
+        Some(index) => &type_and_bang[..index],
+        None => type_and_bang,
+    };
+
+    if is_breaking {
+        return Some(BumpLevel::Major);
+    }
+
+    match commit_type {
+        "feat" => Some(BumpLevel::Minor),
+        "fix" | "perf" => Some(BumpLevel::Patch),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bare_types_map_to_their_bump_level() {
+        assert_eq!(bump_for_message("feat: add x"), Some(BumpLevel::Minor));
+        assert_eq!(bump_for_message("fix: fix x"), Some(BumpLevel::Patch));
+        assert_eq!(bump_for_message("perf: speed up x"), Some(BumpLevel::Patch));
+        assert_eq!(bump_for_message("chore: tidy up"), None);
+    }
+
+    #[test]
+    fn scoped_types_map_to_their_bump_level() {
+        assert_eq!(bump_for_message("feat(parser): add x"), Some(BumpLevel::Minor));
+        assert_eq!(bump_for_message("fix(cli): fix x"), Some(BumpLevel::Patch));
+    }
+
+    #[test]
+    fn bang_and_breaking_footer_force_major() {
+        assert_eq!(bump_for_message("feat!: redo x"), Some(BumpLevel::Major));
+        assert_eq!(bump_for_message("feat(api)!: redo x"), Some(BumpLevel::Major));
+        assert_eq!(
+            bump_for_message("fix: patch\n\nBREAKING CHANGE: removes y"),
+            Some(BumpLevel::Major)
+        );
+    }
+}
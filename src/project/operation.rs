@@ -0,0 +1,48 @@
+use git2::Repository;
+
+use crate::error::SheepError;
+use crate::project::commit_log;
+use crate::project::commit_log::BumpLevel;
+use crate::project::project_version::{ProjectVersion, VersionUpdate};
+
+/// The kind of release the caller wants to perform.
+pub enum Operation {
+    Major,
+    Minor,
+    Patch,
+    /// Derive the bump level from the local commit log instead of being
+    /// told explicitly, following the Conventional Commits convention.
+    Auto,
+}
+
+impl Operation {
+    pub fn version_update(
+        &self,
+        repo: &Repository,
+        project_version: &ProjectVersion,
+    ) -> Result<VersionUpdate, SheepError> {
+        let version_update = match self {
+            Operation::Major => project_version.bump_major(),
+            Operation::Minor => project_version.bump_minor(),
+            Operation::Patch => project_version.bump_patch(),
+            Operation::Auto => self.auto_version_update(repo, project_version)?,
+        };
+        Ok(version_update)
+    }
+
+    fn auto_version_update(
+        &self,
+        repo: &Repository,
+        project_version: &ProjectVersion,
+    ) -> Result<VersionUpdate, SheepError> {
+        let bump = commit_log::highest_bump(repo)?;
+
+        let version_update = match bump {
+            Some(BumpLevel::Major) => project_version.bump_major(),
+            Some(BumpLevel::Minor) => project_version.bump_minor(),
+            Some(BumpLevel::Patch) => project_version.bump_patch(),
+            None => VersionUpdate::NoReleaseNeeded,
+        };
+        Ok(version_update)
+    }
+}
@@ -0,0 +1,101 @@
+use std::path::Path;
+
+use crate::config::SubprojectConfig;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+/// The outcome of computing the next version: either a concrete bump to
+/// apply, or a signal that nothing release-worthy happened so the update
+/// should be skipped.
+pub enum VersionUpdate {
+    Bump(Version),
+    NoReleaseNeeded,
+}
+
+pub struct ProjectVersion {
+    current: Version,
+}
+
+#[cfg_attr(test, mockall::automock)]
+impl ProjectVersion {
+    pub fn new() -> ProjectVersion {
+        ProjectVersion {
+            current: Version { major: 0, minor: 0, patch: 0 },
+        }
+    }
+
+    /// Builds a `ProjectVersion` scoped to one subproject, reading its
+    /// current version from its own version file rather than the root
+    /// project's, so independently-versioned subprojects actually diverge.
+    pub fn new_for_subproject(subproject: &SubprojectConfig) -> ProjectVersion {
+        ProjectVersion {
+            current: read_version_file(&subproject.version_file),
+        }
+    }
+
+    pub fn bump_major(&self) -> VersionUpdate {
+        VersionUpdate::Bump(bump_major(&self.current))
+    }
+
+    pub fn bump_minor(&self) -> VersionUpdate {
+        VersionUpdate::Bump(bump_minor(&self.current))
+    }
+
+    pub fn bump_patch(&self) -> VersionUpdate {
+        VersionUpdate::Bump(bump_patch(&self.current))
+    }
+}
+
+fn bump_major(version: &Version) -> Version {
+    Version { major: version.major + 1, minor: 0, patch: 0 }
+}
+
+fn bump_minor(version: &Version) -> Version {
+    Version { major: version.major, minor: version.minor + 1, patch: 0 }
+}
+
+fn bump_patch(version: &Version) -> Version {
+    Version { major: version.major, minor: version.minor, patch: version.patch + 1 }
+}
+
+/// Reads a `major.minor.patch` version from `path`, defaulting to `0.0.0` if
+/// the file is missing or unparseable.
+fn read_version_file(path: &Path) -> Version {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| parse_version(contents.trim()))
+        .unwrap_or(Version { major: 0, minor: 0, patch: 0 })
+}
+
+fn parse_version(value: &str) -> Option<Version> {
+    let mut parts = value.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    let patch = parts.next()?.parse().ok()?;
+    Some(Version { major, minor, patch })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bumps_increment_and_reset_lower_components() {
+        let version = Version { major: 1, minor: 2, patch: 3 };
+
+        assert_eq!(bump_major(&version), Version { major: 2, minor: 0, patch: 0 });
+        assert_eq!(bump_minor(&version), Version { major: 1, minor: 3, patch: 0 });
+        assert_eq!(bump_patch(&version), Version { major: 1, minor: 2, patch: 4 });
+    }
+
+    #[test]
+    fn parses_well_formed_versions() {
+        assert_eq!(parse_version("1.2.3"), Some(Version { major: 1, minor: 2, patch: 3 }));
+        assert_eq!(parse_version("not-a-version"), None);
+    }
+}
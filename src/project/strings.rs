@@ -0,0 +1,53 @@
+use crate::config::{Config, SubprojectConfig};
+use crate::project::project_version::{Version, VersionUpdate};
+
+/// The branch/commit/tag/remote names derived from a project's configuration
+/// and the version being released.
+pub struct ProjectStrings {
+    pub branch_name: String,
+    pub commit_message: String,
+    pub tag_name: String,
+    pub remote_name: String,
+}
+
+impl ProjectStrings {
+    pub fn new(config: &Config, version_update: &VersionUpdate) -> ProjectStrings {
+        let version = version_string(version_update);
+        ProjectStrings {
+            branch_name: format!("release/v{}", version),
+            commit_message: format!("chore: release v{}", version),
+            tag_name: format!("v{}", version),
+            remote_name: config.repository.remote_name.clone(),
+        }
+    }
+
+    /// Builds the strings for a single subproject release, prefixing the
+    /// tag/branch names with the subproject's configured tag prefix so e.g.
+    /// `my-lib` and `my-app` can be tagged independently in the same repo.
+    /// The commit message uses the subproject's human-readable `name`
+    /// rather than its ref-safe `tag_prefix` as the conventional commit
+    /// scope.
+    pub fn new_for_subproject(
+        config: &Config,
+        subproject: &SubprojectConfig,
+        version_update: &VersionUpdate,
+    ) -> ProjectStrings {
+        let version = version_string(version_update);
+        let tag_name = format!("{}-v{}", subproject.tag_prefix, version);
+        ProjectStrings {
+            branch_name: format!("release/{}", tag_name),
+            commit_message: format!("chore({}): release {}", subproject.name, tag_name),
+            tag_name,
+            remote_name: config.repository.remote_name.clone(),
+        }
+    }
+}
+
+fn version_string(version_update: &VersionUpdate) -> String {
+    match version_update {
+        VersionUpdate::Bump(Version { major, minor, patch }) => {
+            format!("{}.{}.{}", major, minor, patch)
+        }
+        VersionUpdate::NoReleaseNeeded => String::new(),
+    }
+}
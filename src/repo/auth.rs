@@ -0,0 +1,163 @@
+use std::cell::Cell;
+use std::path::PathBuf;
+
+use git2::{Cred, CredentialType, RemoteCallbacks};
+
+use crate::config::AuthConfig;
+
+// Tracks which credential method to try next, since libgit2 re-invokes the
+// credentials callback on rejection and we don't want to retry the same one.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum AuthAttempt {
+    Token,
+    SshAgent,
+    SshKeyFile,
+    InteractivePrompt,
+    Exhausted,
+}
+
+impl AuthAttempt {
+    fn next(self) -> AuthAttempt {
+        match self {
+            AuthAttempt::Token => AuthAttempt::SshAgent,
+            AuthAttempt::SshAgent => AuthAttempt::SshKeyFile,
+            AuthAttempt::SshKeyFile => AuthAttempt::InteractivePrompt,
+            AuthAttempt::InteractivePrompt => AuthAttempt::Exhausted,
+            AuthAttempt::Exhausted => AuthAttempt::Exhausted,
+        }
+    }
+}
+
+// Builds the RemoteCallbacks used for clone/push, trying token, ssh-agent,
+// on-disk ssh key, then an interactive prompt, in that order.
+pub fn callbacks(auth: &AuthConfig) -> RemoteCallbacks<'static> {
+    let auth = auth.clone();
+    let attempt = Cell::new(AuthAttempt::Token);
+    let mut callbacks = RemoteCallbacks::new();
+    callbacks.credentials(move |url, username_from_url, allowed_types| {
+        credentials(&auth, url, username_from_url, allowed_types, &attempt)
+    });
+    callbacks
+}
+
+fn credentials(
+    auth: &AuthConfig,
+    url: &str,
+    username_from_url: Option<&str>,
+    allowed_types: CredentialType,
+    attempt: &Cell<AuthAttempt>,
+) -> Result<Cred, git2::Error> {
+    let username = auth
+        .username
+        .as_deref()
+        .or(username_from_url)
+        .unwrap_or("git");
+
+    loop {
+        let current = attempt.get();
+        attempt.set(current.next());
+
+        let result = match current {
+            AuthAttempt::Token => {
+                if allowed_types.contains(CredentialType::USER_PASS_PLAINTEXT) {
+                    auth.token.as_deref().map(|token| Cred::userpass_plaintext(username, token))
+                } else {
+                    None
+                }
+            }
+            AuthAttempt::SshAgent => {
+                if allowed_types.contains(CredentialType::SSH_KEY) {
+                    Some(Cred::ssh_key_from_agent(username))
+                } else {
+                    None
+                }
+            }
+            AuthAttempt::SshKeyFile => {
+                if allowed_types.contains(CredentialType::SSH_KEY) {
+                    ssh_key_candidates(auth)
+                        .into_iter()
+                        .find_map(|key_path| Cred::ssh_key(username, None, &key_path, None).ok())
+                        .map(Ok)
+                } else {
+                    None
+                }
+            }
+            AuthAttempt::InteractivePrompt => {
+                if auth.allow_interactive_prompt {
+                    Some(prompt(username, url))
+                } else {
+                    None
+                }
+            }
+            AuthAttempt::Exhausted => {
+                return Err(git2::Error::from_str(&format!(
+                    "no usable credentials found for {}",
+                    url
+                )));
+            }
+        };
+
+        if let Some(result) = result {
+            return result;
+        }
+        // This method wasn't applicable (not allowed, or not configured);
+        // move on to the next one without waiting for libgit2 to ask again.
+    }
+}
+
+fn ssh_key_candidates(auth: &AuthConfig) -> Vec<PathBuf> {
+    if let Some(key_path) = &auth.ssh_key_path {
+        return vec![PathBuf::from(key_path)];
+    }
+    let home = match std::env::var_os("HOME") {
+        Some(home) => PathBuf::from(home),
+        None => return vec![],
+    };
+    ["id_rsa", "id_ed25519", "id_ecdsa"]
+        .iter()
+        .map(|name| home.join(".ssh").join(name))
+        .collect()
+}
+
+fn prompt(username: &str, url: &str) -> Result<Cred, git2::Error> {
+    let password = rpassword::prompt_password(format!("Password for '{}@{}': ", username, url))
+        .map_err(|e| git2::Error::from_str(&e.to_string()))?;
+
+    Cred::userpass_plaintext(username, &password)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ssh_key_candidates_prefers_configured_path() {
+        let auth = AuthConfig {
+            ssh_key_path: Some("/custom/key".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(ssh_key_candidates(&auth), vec![PathBuf::from("/custom/key")]);
+    }
+
+    #[test]
+    fn ssh_key_candidates_falls_back_to_default_locations() {
+        let auth = AuthConfig::default();
+
+        let candidates = ssh_key_candidates(&auth);
+
+        if let Some(home) = std::env::var_os("HOME") {
+            let home = PathBuf::from(home);
+            assert_eq!(
+                candidates,
+                vec![
+                    home.join(".ssh").join("id_rsa"),
+                    home.join(".ssh").join("id_ed25519"),
+                    home.join(".ssh").join("id_ecdsa"),
+                ]
+            );
+        } else {
+            assert!(candidates.is_empty());
+        }
+    }
+}
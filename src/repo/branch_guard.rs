@@ -0,0 +1,99 @@
+use git2::Repository;
+
+use crate::error::SheepError;
+
+/// Checks the repo's current branch against `allow_branch` (a comma
+/// separated list of glob patterns, e.g. `main,master` or `release/*`) and
+/// errors out if none match. This guards against accidentally cutting a
+/// release from a feature branch.
+///
+/// `branch_override` is used instead of the repo's own `HEAD` when checking
+/// a dry-run project, so the guard evaluates against the original local
+/// repo's branch rather than the dry-run mirror's.
+pub fn check(
+    repo: &Repository,
+    allow_branch: &str,
+    branch_override: Option<&str>,
+) -> Result<(), SheepError> {
+    let current_branch = match branch_override {
+        Some(branch) => branch.to_string(),
+        None => current_branch_name(repo)?,
+    };
+
+    let allowed = allow_branch
+        .split(',')
+        .map(str::trim)
+        .any(|pattern| matches_glob(pattern, &current_branch));
+
+    if !allowed {
+        return Err(git2::Error::from_str(&format!(
+            "current branch '{}' does not match allowed branch pattern '{}'",
+            current_branch, allow_branch
+        ))
+        .into());
+    }
+
+    Ok(())
+}
+
+fn current_branch_name(repo: &Repository) -> Result<String, SheepError> {
+    let head = repo.head()?;
+    let branch_name = head
+        .shorthand()
+        .ok_or_else(|| git2::Error::from_str("HEAD is not a valid branch"))?;
+    Ok(branch_name.to_string())
+}
+
+/// Minimal glob matcher supporting `*` (any run of characters). That's all
+/// `allow_branch` patterns like `main`, `master`, or `release/*` need.
+fn matches_glob(pattern: &str, value: &str) -> bool {
+    let mut segments = pattern.split('*').peekable();
+    let mut remainder = value;
+
+    if let Some(first) = segments.peek() {
+        if !pattern.starts_with('*') {
+            match remainder.strip_prefix(*first) {
+                Some(rest) => remainder = rest,
+                None => return false,
+            }
+            segments.next();
+        }
+    }
+
+    while let Some(segment) = segments.next() {
+        if segment.is_empty() {
+            continue;
+        }
+        if segments.peek().is_none() {
+            // The last segment is anchored to the end of the value.
+            return remainder.ends_with(segment);
+        }
+        match remainder.find(segment) {
+            Some(index) => remainder = &remainder[index + segment.len()..],
+            None => return false,
+        }
+    }
+
+    pattern.ends_with('*') || remainder.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_patterns_require_an_exact_match() {
+        assert!(matches_glob("main", "main"));
+        assert!(!matches_glob("main", "mainline"));
+        assert!(!matches_glob("main", "not-main"));
+    }
+
+    #[test]
+    fn wildcard_patterns_match_a_prefix_or_suffix() {
+        assert!(matches_glob("release/*", "release/v1.2.3"));
+        assert!(!matches_glob("release/*", "feature/x"));
+        assert!(matches_glob("*", "anything"));
+        assert!(matches_glob("*-hotfix", "v1-hotfix-hotfix"));
+        assert!(!matches_glob("*-hotfix", "v1-hotfix-2"));
+    }
+}
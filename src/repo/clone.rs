@@ -0,0 +1,30 @@
+use std::path::Path;
+
+use git2::{build::RepoBuilder, FetchOptions, RemoteCallbacks, Repository};
+
+use crate::error::SheepError;
+
+pub struct GitCloner;
+
+impl GitCloner {
+    pub fn new() -> GitCloner {
+        GitCloner
+    }
+
+    /// Clones `url` into `path`, authenticating via `callbacks` so private
+    /// remotes (SSH or token-protected) can be cloned too.
+    pub fn clone<P: AsRef<Path>>(
+        &self,
+        url: &str,
+        path: P,
+        callbacks: RemoteCallbacks,
+    ) -> Result<Repository, SheepError> {
+        let mut fetch_options = FetchOptions::new();
+        fetch_options.remote_callbacks(callbacks);
+
+        let repo = RepoBuilder::new()
+            .fetch_options(fetch_options)
+            .clone(url, path.as_ref())?;
+        Ok(repo)
+    }
+}
@@ -0,0 +1,65 @@
+use std::path::PathBuf;
+
+use git2::Repository;
+
+use crate::error::SheepError;
+use crate::repo::sign::{self, SigningConfig};
+
+pub struct GitCommits;
+
+impl GitCommits {
+    pub fn new() -> GitCommits {
+        GitCommits
+    }
+
+    /// Stages everything under `paths` (or the whole worktree, if empty) and
+    /// creates a commit with `message`. `paths` are pathspecs rather than
+    /// individual files, so a subproject directory stages only its own
+    /// files. If `signing` is set, the commit is GPG-signed instead of being
+    /// created through the usual unsigned path.
+    pub fn commit(
+        &self,
+        repo: &Repository,
+        paths: Vec<PathBuf>,
+        message: &str,
+        signing: Option<&SigningConfig>,
+    ) -> Result<(), SheepError> {
+        let mut index = repo.index()?;
+        if paths.is_empty() {
+            index.add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)?;
+        } else {
+            index.add_all(paths.iter(), git2::IndexAddOption::DEFAULT, None)?;
+        }
+        index.write()?;
+
+        let tree_oid = index.write_tree()?;
+        let tree = repo.find_tree(tree_oid)?;
+        let signature = repo.signature()?;
+        let parent = repo.head()?.peel_to_commit()?;
+
+        match signing {
+            Some(signing) => {
+                let buffer =
+                    repo.commit_create_buffer(&signature, &signature, message, &tree, &[&parent])?;
+                let buffer = buffer
+                    .as_str()
+                    .ok_or_else(|| git2::Error::from_str("commit buffer was not valid UTF-8"))?;
+                let signature_armor = sign::sign(buffer, signing)?;
+                let commit_oid = repo.commit_signed(buffer, &signature_armor, None)?;
+
+                let head_ref_name = repo.head()?.name().unwrap_or("HEAD").to_string();
+                repo.reference(
+                    &head_ref_name,
+                    commit_oid,
+                    true,
+                    "sheep_it: signed release commit",
+                )?;
+            }
+            None => {
+                repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &[&parent])?;
+            }
+        }
+
+        Ok(())
+    }
+}
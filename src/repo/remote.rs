@@ -0,0 +1,59 @@
+use git2::{PushOptions, RemoteCallbacks, Repository};
+
+use crate::error::SheepError;
+
+pub struct GitRemotes;
+
+impl GitRemotes {
+    pub fn new() -> GitRemotes {
+        GitRemotes
+    }
+
+    pub fn remote_url(&self, repo: &Repository, remote_name: &str) -> Result<String, SheepError> {
+        let remote = repo.find_remote(remote_name)?;
+        let url = remote
+            .url()
+            .ok_or_else(|| git2::Error::from_str("remote has no URL"))?;
+        Ok(url.to_string())
+    }
+
+    /// Pushes `branch_name` to `remote_name`, authenticating via
+    /// `callbacks` so private remotes can be pushed to.
+    pub fn push_branch(
+        &self,
+        repo: &Repository,
+        branch_name: &str,
+        remote_name: &str,
+        callbacks: RemoteCallbacks,
+    ) -> Result<(), SheepError> {
+        let refspec = format!("refs/heads/{}:refs/heads/{}", branch_name, branch_name);
+        self.push(repo, remote_name, &refspec, callbacks)
+    }
+
+    /// Pushes `tag_name` to `remote_name`, authenticating via `callbacks` so
+    /// private remotes can be pushed to.
+    pub fn push_tag(
+        &self,
+        repo: &Repository,
+        tag_name: &str,
+        remote_name: &str,
+        callbacks: RemoteCallbacks,
+    ) -> Result<(), SheepError> {
+        let refspec = format!("refs/tags/{}:refs/tags/{}", tag_name, tag_name);
+        self.push(repo, remote_name, &refspec, callbacks)
+    }
+
+    fn push(
+        &self,
+        repo: &Repository,
+        remote_name: &str,
+        refspec: &str,
+        callbacks: RemoteCallbacks,
+    ) -> Result<(), SheepError> {
+        let mut remote = repo.find_remote(remote_name)?;
+        let mut push_options = PushOptions::new();
+        push_options.remote_callbacks(callbacks);
+        remote.push(&[refspec], Some(&mut push_options))?;
+        Ok(())
+    }
+}
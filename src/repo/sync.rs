@@ -0,0 +1,63 @@
+use git2::{AutotagOption, FetchOptions, Repository};
+
+use crate::error::SheepError;
+use crate::repo::auth;
+use crate::config::AuthConfig;
+
+/// Fetches `remote_name` and fast-forwards the current branch to its
+/// upstream tip before a release touches anything, so sheep_it never tags a
+/// commit that's already stale or fails the push at the very end. Returns an
+/// error if the local branch has diverged from the remote and can't be
+/// fast-forwarded.
+pub fn sync(repo: &Repository, remote_name: &str, auth: &AuthConfig) -> Result<(), SheepError> {
+    let mut remote = repo.find_remote(remote_name)?;
+
+    let mut fetch_options = FetchOptions::new();
+    fetch_options.remote_callbacks(auth::callbacks(auth));
+    fetch_options.download_tags(AutotagOption::None);
+
+    let head = repo.head()?;
+    let branch_name = head
+        .shorthand()
+        .ok_or_else(|| git2::Error::from_str("HEAD is not a valid branch"))?
+        .to_string();
+
+    remote.fetch(&[branch_name.as_str()], Some(&mut fetch_options), None)?;
+
+    let upstream_ref_name = format!("refs/remotes/{}/{}", remote_name, branch_name);
+    let upstream = repo.find_reference(&upstream_ref_name)?;
+    let upstream_oid = upstream
+        .target()
+        .ok_or_else(|| git2::Error::from_str("upstream reference has no target"))?;
+
+    let local_oid = head
+        .target()
+        .ok_or_else(|| git2::Error::from_str("HEAD has no target"))?;
+
+    if local_oid == upstream_oid {
+        return Ok(());
+    }
+
+    let (analysis, _) = repo.merge_analysis(&[&repo.find_annotated_commit(upstream_oid)?])?;
+    if analysis.is_up_to_date() {
+        // Upstream is already an ancestor of local, i.e. local is ahead -
+        // nothing to fast-forward. `merge_analysis` reports this case as up
+        // to date, not fast-forward.
+        return Ok(());
+    }
+    if !analysis.is_fast_forward() {
+        return Err(git2::Error::from_str(&format!(
+            "local branch '{}' has diverged from '{}' and cannot be fast-forwarded",
+            branch_name, upstream_ref_name
+        ))
+        .into());
+    }
+
+    let local_ref_name = format!("refs/heads/{}", branch_name);
+    let mut local_ref = repo.find_reference(&local_ref_name)?;
+    local_ref.set_target(upstream_oid, "sheep_it: fast-forward before release")?;
+    repo.set_head(&local_ref_name)?;
+    repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))?;
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use git2::Repository;
+
+use crate::error::SheepError;
+use crate::repo::sign::{self, SigningConfig};
+
+pub struct GitTags;
+
+impl GitTags {
+    pub fn new() -> GitTags {
+        GitTags
+    }
+
+    /// Creates an annotated tag named `name` pointing at `HEAD`, with an
+    /// optional `message`. If `signing` is set, the tag is GPG-signed so it
+    /// shows up as verified on the forge.
+    pub fn create_tag(
+        &self,
+        repo: &Repository,
+        name: &str,
+        message: Option<&str>,
+        signing: Option<&SigningConfig>,
+    ) -> Result<(), SheepError> {
+        let target = repo.head()?.peel_to_commit()?;
+        let signature = repo.signature()?;
+        let message = message.unwrap_or(name);
+
+        match signing {
+            Some(signing) => {
+                let buffer = tag_buffer(&target, &signature, name, message);
+                let signature_armor = sign::sign(&buffer, signing)?;
+                let signed_buffer = format!("{}{}", buffer, signature_armor);
+                let tag_oid = repo
+                    .odb()?
+                    .write(git2::ObjectType::Tag, signed_buffer.as_bytes())?;
+                repo.reference(
+                    &format!("refs/tags/{}", name),
+                    tag_oid,
+                    false,
+                    "sheep_it: signed release tag",
+                )?;
+            }
+            None => {
+                repo.tag(name, target.as_object(), &signature, message, false)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Hand-builds the raw tag object buffer, since git2-rs has no
+/// buffer-returning equivalent of `git_tag_create` the way
+/// `commit_create_buffer` exists for commits. Needed so the buffer can be
+/// signed with `gpg` before it's written to the odb.
+fn tag_buffer(target: &git2::Commit, tagger: &git2::Signature, name: &str, message: &str) -> String {
+    let when = tagger.when();
+    let offset_minutes = when.offset_minutes().abs();
+
+    format!(
+        "object {}\ntype commit\ntag {}\ntagger {} <{}> {} {}{:02}{:02}\n\n{}\n",
+        target.id(),
+        name,
+        tagger.name().unwrap_or(""),
+        tagger.email().unwrap_or(""),
+        when.seconds(),
+        when.sign(),
+        offset_minutes / 60,
+        offset_minutes % 60,
+        message,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tag_buffer_matches_the_git_tag_object_format() {
+        let dir = std::env::temp_dir().join(format!("sheep_it_tag_buffer_test_{}", std::process::id()));
+        let repo = Repository::init(&dir).expect("init temp repo");
+
+        let signature = git2::Signature::new("Release Bot", "bot@example.com", &git2::Time::new(1_700_000_000, 60))
+            .expect("build signature");
+        let tree_oid = repo.treebuilder(None).unwrap().write().unwrap();
+        let tree = repo.find_tree(tree_oid).unwrap();
+        let commit_oid = repo
+            .commit(None, &signature, &signature, "initial commit", &tree, &[])
+            .expect("create commit");
+        let commit = repo.find_commit(commit_oid).unwrap();
+
+        let buffer = tag_buffer(&commit, &signature, "v1.0.0", "release v1.0.0");
+
+        let expected = format!(
+            "object {}\ntype commit\ntag v1.0.0\ntagger Release Bot <bot@example.com> 1700000000 +0100\n\nrelease v1.0.0\n",
+            commit_oid
+        );
+        assert_eq!(buffer, expected);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}